@@ -1,5 +1,10 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(all(feature = "critical-section", not(target_has_atomic = "ptr")))]
+use core::cell::UnsafeCell;
 use core::{
     fmt::{Debug, Pointer},
     hash::Hash,
@@ -155,6 +160,22 @@ impl<T> MaybeNull<T> {
         self.ptr.map_or(0, |ptr| ptr.addr().get())
     }
 
+    /// Look at [`ptr::with_addr`] for more information.
+    ///
+    /// If `addr` is `0`, the result is a null `MaybeNull`.
+    #[inline]
+    pub fn with_addr(self, addr: usize) -> Self {
+        self.map(|ptr| ptr.with_addr(addr))
+    }
+
+    /// Look at [`ptr::map_addr`] for more information.
+    ///
+    /// If `f` returns `0`, the result is a null `MaybeNull`.
+    #[inline]
+    pub fn map_addr(self, f: impl FnOnce(usize) -> usize) -> Self {
+        self.map(|ptr| ptr.map_addr(f))
+    }
+
     /// Look at [`ptr::as_ref`] for more information.
     #[allow(clippy::missing_safety_doc)]
     #[inline]
@@ -234,15 +255,384 @@ impl<T> MaybeNull<T> {
     pub fn wrapping_byte_sub(self, count: usize) -> Self {
         self.map(|ptr| ptr.wrapping_byte_sub(count))
     }
+
+    /// Returns a copy of `self` with `tag` stashed in the low bits made
+    /// spare by `T`'s alignment (the "marked pointer" technique used by
+    /// reclamation schemes).
+    ///
+    /// `tag` is masked to `align_of::<T>() - 1` before being ORed into the
+    /// address; use [`split_tag`](Self::split_tag) to recover it.
+    #[inline]
+    pub fn with_tag(self, tag: usize) -> Self {
+        let mask = core::mem::align_of::<T>() - 1;
+        self.map(|ptr| ptr.map_addr(|addr| addr | (tag & mask)))
+    }
+
+    /// Splits `self` into the untagged pointer and the tag bits previously
+    /// set with [`with_tag`](Self::with_tag).
+    #[inline]
+    pub fn split_tag(self) -> (Self, usize) {
+        let mask = core::mem::align_of::<T>() - 1;
+        let tag = self.addr() & mask;
+        (self.map(|ptr| ptr.map_addr(|addr| addr & !mask)), tag)
+    }
+
+    /// Returns `true` if the pointer is null once any tag bits set by
+    /// [`with_tag`](Self::with_tag) are masked off.
+    ///
+    /// Unlike [`is_null`](Self::is_null), which only considers the raw
+    /// address, this treats a null base pointer carrying a nonzero tag as
+    /// still null.
+    #[inline]
+    pub fn is_null_untagged(self) -> bool {
+        self.split_tag().0.is_null()
+    }
+}
+
+#[cfg(test)]
+mod maybe_null_tag_tests {
+    use super::*;
+
+    #[test]
+    fn with_tag_masks_to_alignment_and_split_tag_recovers_it() {
+        let ptr = MaybeNull::new(&mut 0u64 as *mut u64);
+        let tagged = ptr.with_tag(0b1111);
+        // `u64` is 8-byte aligned, so only the low 3 bits are available;
+        // the top bit of the tag must be dropped.
+        let (untagged, tag) = tagged.split_tag();
+        assert_eq!(tag, 0b111);
+        assert_eq!(untagged, ptr);
+    }
+
+    #[test]
+    fn with_tag_on_null_is_still_null_once_untagged() {
+        let tagged = MaybeNull::<u64>::null().with_tag(0b10);
+        assert!(!tagged.is_null());
+        assert!(tagged.is_null_untagged());
+    }
+
+    #[test]
+    fn split_tag_on_untagged_pointer_returns_zero() {
+        let ptr = MaybeNull::new(&mut 0u64 as *mut u64);
+        let (untagged, tag) = ptr.split_tag();
+        assert_eq!(tag, 0);
+        assert_eq!(untagged, ptr);
+    }
+
+    #[test]
+    fn atomic_fetch_or_and_xor_flip_tag_bits_without_disturbing_the_base_pointer() {
+        let mut value = 0u64;
+        let base = MaybeNull::new(&mut value as *mut u64);
+        let atomic = AtomicMaybeNull::new(base.get_unchecked());
+
+        let prev = atomic.fetch_or(0b1, Ordering::Relaxed);
+        assert_eq!(prev.get_unchecked(Ordering::Relaxed), base.get_unchecked());
+        let (untagged, tag) = MaybeNull::new(atomic.get_unchecked(Ordering::Relaxed)).split_tag();
+        assert_eq!(tag, 0b1);
+        assert_eq!(untagged, base);
+
+        atomic.fetch_xor(0b11, Ordering::Relaxed);
+        let (untagged, tag) = MaybeNull::new(atomic.get_unchecked(Ordering::Relaxed)).split_tag();
+        assert_eq!(tag, 0b10);
+        assert_eq!(untagged, base);
+
+        atomic.fetch_and(!0b111usize, Ordering::Relaxed);
+        let (untagged, tag) = MaybeNull::new(atomic.get_unchecked(Ordering::Relaxed)).split_tag();
+        assert_eq!(tag, 0);
+        assert_eq!(untagged, base);
+    }
+}
+
+/// The storage backing [`AtomicMaybeNull`].
+///
+/// On targets with a native `AtomicPtr` this is a thin wrapper around it. On
+/// targets without one (e.g. `thumbv6m`), enabling the `critical-section`
+/// feature swaps it for an [`UnsafeCell`] guarded by `critical_section::with`,
+/// which ignores the requested [`Ordering`] since the critical section already
+/// provides mutual exclusion. This mirrors the polyfill strategy used by the
+/// `atomic-polyfill` crate for integer atomics.
+#[cfg(any(not(feature = "critical-section"), target_has_atomic = "ptr"))]
+#[repr(transparent)]
+struct Inner<T>(AtomicPtr<T>);
+
+#[cfg(any(not(feature = "critical-section"), target_has_atomic = "ptr"))]
+impl<T> Inner<T> {
+    #[inline]
+    const fn new(ptr: *mut T) -> Self {
+        Self(AtomicPtr::new(ptr))
+    }
+
+    #[inline]
+    fn load(&self, order: Ordering) -> *mut T {
+        self.0.load(order)
+    }
+
+    #[inline]
+    fn store(&self, ptr: *mut T, order: Ordering) {
+        self.0.store(ptr, order);
+    }
+
+    #[inline]
+    fn swap(&self, ptr: *mut T, order: Ordering) -> *mut T {
+        self.0.swap(ptr, order)
+    }
+
+    #[inline]
+    fn compare_exchange(
+        &self,
+        current: *mut T,
+        new: *mut T,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<*mut T, *mut T> {
+        self.0.compare_exchange(current, new, success, failure)
+    }
+
+    #[inline]
+    fn compare_exchange_weak(
+        &self,
+        current: *mut T,
+        new: *mut T,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<*mut T, *mut T> {
+        self.0.compare_exchange_weak(current, new, success, failure)
+    }
+
+    #[inline]
+    fn fetch_update(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: impl FnMut(*mut T) -> Option<*mut T>,
+    ) -> Result<*mut T, *mut T> {
+        self.0.fetch_update(set_order, fetch_order, f)
+    }
+
+    #[inline]
+    fn fetch_ptr_add(&self, val: usize, order: Ordering) -> *mut T {
+        self.0.fetch_ptr_add(val, order)
+    }
+
+    #[inline]
+    fn fetch_ptr_sub(&self, val: usize, order: Ordering) -> *mut T {
+        self.0.fetch_ptr_sub(val, order)
+    }
+
+    #[inline]
+    fn fetch_byte_add(&self, val: usize, order: Ordering) -> *mut T {
+        self.0.fetch_byte_add(val, order)
+    }
+
+    #[inline]
+    fn fetch_byte_sub(&self, val: usize, order: Ordering) -> *mut T {
+        self.0.fetch_byte_sub(val, order)
+    }
+
+    /// Reinterprets the underlying `AtomicPtr<T>` as an `AtomicUsize` over
+    /// the same storage.
+    ///
+    /// Sound because `AtomicPtr<T>` and `AtomicUsize` both store a single
+    /// pointer-sized machine word, so the two have the same size, alignment
+    /// and bit-pattern validity; this is what lets `fetch_or`/`fetch_and`/
+    /// `fetch_xor` below compile to one atomic RMW instruction instead of a
+    /// compare-exchange retry loop.
+    #[inline]
+    fn as_atomic_usize(&self) -> &core::sync::atomic::AtomicUsize {
+        unsafe { &*(&self.0 as *const AtomicPtr<T> as *const core::sync::atomic::AtomicUsize) }
+    }
+
+    #[inline]
+    fn fetch_or(&self, val: usize, order: Ordering) -> *mut T {
+        ptr::with_exposed_provenance_mut(self.as_atomic_usize().fetch_or(val, order))
+    }
+
+    #[inline]
+    fn fetch_and(&self, val: usize, order: Ordering) -> *mut T {
+        ptr::with_exposed_provenance_mut(self.as_atomic_usize().fetch_and(val, order))
+    }
+
+    #[inline]
+    fn fetch_xor(&self, val: usize, order: Ordering) -> *mut T {
+        ptr::with_exposed_provenance_mut(self.as_atomic_usize().fetch_xor(val, order))
+    }
+}
+
+#[cfg(any(not(feature = "critical-section"), target_has_atomic = "ptr"))]
+impl<T> Debug for Inner<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+#[cfg(any(not(feature = "critical-section"), target_has_atomic = "ptr"))]
+impl<T> Pointer for Inner<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.0, f)
+    }
+}
+
+#[cfg(all(feature = "critical-section", not(target_has_atomic = "ptr")))]
+#[repr(transparent)]
+struct Inner<T>(UnsafeCell<*mut T>);
+
+#[cfg(all(feature = "critical-section", not(target_has_atomic = "ptr")))]
+unsafe impl<T> Send for Inner<T> {}
+#[cfg(all(feature = "critical-section", not(target_has_atomic = "ptr")))]
+unsafe impl<T> Sync for Inner<T> {}
+
+#[cfg(all(feature = "critical-section", not(target_has_atomic = "ptr")))]
+impl<T> Inner<T> {
+    #[inline]
+    const fn new(ptr: *mut T) -> Self {
+        Self(UnsafeCell::new(ptr))
+    }
+
+    #[inline]
+    fn load(&self, _order: Ordering) -> *mut T {
+        critical_section::with(|_| unsafe { *self.0.get() })
+    }
+
+    #[inline]
+    fn store(&self, ptr: *mut T, _order: Ordering) {
+        critical_section::with(|_| unsafe {
+            *self.0.get() = ptr;
+        });
+    }
+
+    #[inline]
+    fn swap(&self, ptr: *mut T, _order: Ordering) -> *mut T {
+        critical_section::with(|_| unsafe {
+            let cell = self.0.get();
+            let prev = *cell;
+            *cell = ptr;
+            prev
+        })
+    }
+
+    #[inline]
+    fn compare_exchange(
+        &self,
+        current: *mut T,
+        new: *mut T,
+        _success: Ordering,
+        _failure: Ordering,
+    ) -> Result<*mut T, *mut T> {
+        critical_section::with(|_| unsafe {
+            let cell = self.0.get();
+            let prev = *cell;
+            if prev == current {
+                *cell = new;
+                Ok(prev)
+            } else {
+                Err(prev)
+            }
+        })
+    }
+
+    #[inline]
+    fn compare_exchange_weak(
+        &self,
+        current: *mut T,
+        new: *mut T,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<*mut T, *mut T> {
+        self.compare_exchange(current, new, success, failure)
+    }
+
+    #[inline]
+    fn fetch_update(
+        &self,
+        _set_order: Ordering,
+        _fetch_order: Ordering,
+        mut f: impl FnMut(*mut T) -> Option<*mut T>,
+    ) -> Result<*mut T, *mut T> {
+        critical_section::with(|_| unsafe {
+            let cell = self.0.get();
+            let prev = *cell;
+            match f(prev) {
+                Some(new) => {
+                    *cell = new;
+                    Ok(prev)
+                }
+                None => Err(prev),
+            }
+        })
+    }
+
+    #[inline]
+    fn fetch_ptr_add(&self, val: usize, order: Ordering) -> *mut T {
+        self.fetch_update(order, order, |ptr| Some(ptr.wrapping_add(val)))
+            .unwrap()
+    }
+
+    #[inline]
+    fn fetch_ptr_sub(&self, val: usize, order: Ordering) -> *mut T {
+        self.fetch_update(order, order, |ptr| Some(ptr.wrapping_sub(val)))
+            .unwrap()
+    }
+
+    #[inline]
+    fn fetch_byte_add(&self, val: usize, order: Ordering) -> *mut T {
+        self.fetch_update(order, order, |ptr| Some(ptr.wrapping_byte_add(val)))
+            .unwrap()
+    }
+
+    #[inline]
+    fn fetch_byte_sub(&self, val: usize, order: Ordering) -> *mut T {
+        self.fetch_update(order, order, |ptr| Some(ptr.wrapping_byte_sub(val)))
+            .unwrap()
+    }
+
+    #[inline]
+    fn fetch_or(&self, val: usize, order: Ordering) -> *mut T {
+        self.fetch_update(order, order, |ptr| Some(ptr.map_addr(|addr| addr | val)))
+            .unwrap()
+    }
+
+    #[inline]
+    fn fetch_and(&self, val: usize, order: Ordering) -> *mut T {
+        self.fetch_update(order, order, |ptr| Some(ptr.map_addr(|addr| addr & val)))
+            .unwrap()
+    }
+
+    #[inline]
+    fn fetch_xor(&self, val: usize, order: Ordering) -> *mut T {
+        self.fetch_update(order, order, |ptr| Some(ptr.map_addr(|addr| addr ^ val)))
+            .unwrap()
+    }
+}
+
+#[cfg(all(feature = "critical-section", not(target_has_atomic = "ptr")))]
+impl<T> Debug for Inner<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.load(Ordering::SeqCst), f)
+    }
+}
+
+#[cfg(all(feature = "critical-section", not(target_has_atomic = "ptr")))]
+impl<T> Pointer for Inner<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.load(Ordering::SeqCst), f)
+    }
 }
 
 /// A wrapper around `core::sync::atomic::AtomicPtr` that represents a pointer
 /// that is checked for null before accessed.
 ///
 /// AtomicMaybeNull is marked as `repr(transparent)`.
+///
+/// With the `critical-section` feature enabled, targets without a native
+/// `AtomicPtr` (`target_has_atomic = "ptr"` unset) fall back to a
+/// `critical_section`-guarded cell; see [`Inner`] for details.
 #[repr(transparent)]
 pub struct AtomicMaybeNull<T> {
-    ptr: AtomicPtr<T>,
+    ptr: Inner<T>,
 }
 
 impl<T> Debug for AtomicMaybeNull<T> {
@@ -263,14 +653,14 @@ impl<T> AtomicMaybeNull<T> {
     #[inline]
     pub const fn new(ptr: *mut T) -> Self {
         Self {
-            ptr: AtomicPtr::new(ptr),
+            ptr: Inner::new(ptr),
         }
     }
 
     #[inline]
     pub const fn from_non_null(ptr: NonNull<T>) -> Self {
         Self {
-            ptr: AtomicPtr::new(ptr.as_ptr()),
+            ptr: Inner::new(ptr.as_ptr()),
         }
     }
 
@@ -387,4 +777,325 @@ impl<T> AtomicMaybeNull<T> {
             .map(Self::new)
             .map_err(Self::new)
     }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_ptr_add`] for more information.
+    #[inline]
+    pub fn fetch_ptr_add(&self, val: usize, order: Ordering) -> Self {
+        Self::new(self.ptr.fetch_ptr_add(val, order))
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_ptr_sub`] for more information.
+    #[inline]
+    pub fn fetch_ptr_sub(&self, val: usize, order: Ordering) -> Self {
+        Self::new(self.ptr.fetch_ptr_sub(val, order))
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_byte_add`] for more information.
+    #[inline]
+    pub fn fetch_byte_add(&self, val: usize, order: Ordering) -> Self {
+        Self::new(self.ptr.fetch_byte_add(val, order))
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_byte_sub`] for more information.
+    #[inline]
+    pub fn fetch_byte_sub(&self, val: usize, order: Ordering) -> Self {
+        Self::new(self.ptr.fetch_byte_sub(val, order))
+    }
+
+    /// Atomically ORs `val` into the pointer's address and returns the
+    /// previous value.
+    ///
+    /// On the native `AtomicPtr` backend this is a single atomic RMW
+    /// instruction (via a same-layout cast to `AtomicUsize`), not a
+    /// compare-exchange loop; intended for flipping tag bits set by
+    /// [`MaybeNull::with_tag`], e.g. to mark a node logically deleted.
+    #[inline]
+    pub fn fetch_or(&self, val: usize, order: Ordering) -> Self {
+        Self::new(self.ptr.fetch_or(val, order))
+    }
+
+    /// Atomically ANDs `val` into the pointer's address and returns the
+    /// previous value.
+    ///
+    /// On the native `AtomicPtr` backend this is a single atomic RMW
+    /// instruction (via a same-layout cast to `AtomicUsize`), not a
+    /// compare-exchange loop.
+    #[inline]
+    pub fn fetch_and(&self, val: usize, order: Ordering) -> Self {
+        Self::new(self.ptr.fetch_and(val, order))
+    }
+
+    /// Atomically XORs `val` into the pointer's address and returns the
+    /// previous value.
+    ///
+    /// On the native `AtomicPtr` backend this is a single atomic RMW
+    /// instruction (via a same-layout cast to `AtomicUsize`), not a
+    /// compare-exchange loop.
+    #[inline]
+    pub fn fetch_xor(&self, val: usize, order: Ordering) -> Self {
+        Self::new(self.ptr.fetch_xor(val, order))
+    }
+
+    /// Like [`compare_exchange`](Self::compare_exchange), but compares and
+    /// installs `(pointer, tag)` pairs as produced by [`MaybeNull::with_tag`].
+    #[inline]
+    pub fn compare_exchange_tagged(
+        &self,
+        current: *mut T,
+        current_tag: usize,
+        new: *mut T,
+        new_tag: usize,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self> {
+        let current = MaybeNull::new(current)
+            .with_tag(current_tag)
+            .get_unchecked();
+        let new = MaybeNull::new(new).with_tag(new_tag).get_unchecked();
+        self.compare_exchange(current, new, success, failure)
+    }
+}
+
+#[cfg(test)]
+mod atomic_maybe_null_tests {
+    use super::*;
+
+    // These exercise `Inner`'s public surface through `AtomicMaybeNull`, so
+    // they run against whichever backend the target selects: the native
+    // `AtomicPtr` backend here (this target has `target_has_atomic = "ptr"`),
+    // or the `critical-section` fallback on a target without one. Both
+    // backends implement the same method set, so the same test bodies cover
+    // either one; there's no way to force the fallback backend from a host
+    // that has native pointer atomics.
+
+    #[test]
+    fn new_is_null_and_get_roundtrips_a_value() {
+        let mut value = 0u64;
+        let atomic = AtomicMaybeNull::<u64>::null();
+        assert!(atomic.is_null(Ordering::Relaxed));
+
+        atomic.set(
+            NonNull::new(&mut value as *mut u64).unwrap(),
+            Ordering::Relaxed,
+        );
+        assert_eq!(
+            atomic.get_unchecked(Ordering::Relaxed),
+            &mut value as *mut u64
+        );
+
+        atomic.nullify(Ordering::Relaxed);
+        assert!(atomic.is_null(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn swap_installs_the_new_value_and_returns_the_old_one() {
+        let (mut a, mut b) = (1u64, 2u64);
+        let atomic = AtomicMaybeNull::new(&mut a as *mut u64);
+
+        let prev = atomic.swap(&mut b as *mut u64, Ordering::Relaxed);
+        assert_eq!(prev.get_unchecked(Ordering::Relaxed), &mut a as *mut u64);
+        assert_eq!(atomic.get_unchecked(Ordering::Relaxed), &mut b as *mut u64);
+    }
+
+    #[test]
+    fn compare_exchange_succeeds_on_match_and_fails_otherwise() {
+        let (mut a, mut b, mut c) = (1u64, 2u64, 3u64);
+        let atomic = AtomicMaybeNull::new(&mut a as *mut u64);
+
+        let err = atomic
+            .compare_exchange(
+                &mut b as *mut u64,
+                &mut c as *mut u64,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .unwrap_err();
+        assert_eq!(err.get_unchecked(Ordering::Relaxed), &mut a as *mut u64);
+
+        atomic
+            .compare_exchange(
+                &mut a as *mut u64,
+                &mut c as *mut u64,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .unwrap();
+        assert_eq!(atomic.get_unchecked(Ordering::Relaxed), &mut c as *mut u64);
+    }
+
+    #[test]
+    fn fetch_update_applies_the_closure_once_it_returns_some() {
+        let (mut a, mut b) = (1u64, 2u64);
+        let atomic = AtomicMaybeNull::new(&mut a as *mut u64);
+
+        let prev = atomic
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |_| {
+                Some(&mut b as *mut u64)
+            })
+            .unwrap();
+        assert_eq!(prev.get_unchecked(Ordering::Relaxed), &mut a as *mut u64);
+        assert_eq!(atomic.get_unchecked(Ordering::Relaxed), &mut b as *mut u64);
+    }
+}
+
+/// A lock-free, single-assignment slot built on top of [`AtomicMaybeNull`].
+///
+/// `AtomicSetOnce<T>` owns the allocation it stores: once
+/// [`set_if_none`](Self::set_if_none) installs a value, the slot holds it
+/// until [`take`](Self::take) removes it or the slot is dropped. This is the
+/// building block behind lazily-initialized globals and lock-free linked
+/// lists, without hand-rolled unsafe pointer bookkeeping.
+#[cfg(feature = "alloc")]
+pub struct AtomicSetOnce<T> {
+    ptr: AtomicMaybeNull<T>,
+    // `AtomicMaybeNull<T>`'s storage is unconditionally `Send`/`Sync`
+    // regardless of `T` (it's a raw pointer underneath), so without this
+    // marker the slot would auto-derive unconditional `Send`/`Sync` even
+    // though it safely hands out `&T`/`Box<T>`. This blocks the auto-derive
+    // so the bounded impls below are the only ones that apply.
+    _marker: core::marker::PhantomData<*mut T>,
+}
+
+// Safety: the slot only ever exposes `T` by value (`Box<T>`) or by shared
+// reference (`&T`), matching `std::sync::OnceLock`'s bounds.
+#[cfg(feature = "alloc")]
+unsafe impl<T: Send> Send for AtomicSetOnce<T> {}
+#[cfg(feature = "alloc")]
+unsafe impl<T: Send + Sync> Sync for AtomicSetOnce<T> {}
+
+#[cfg(feature = "alloc")]
+impl<T> Default for AtomicSetOnce<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> AtomicSetOnce<T> {
+    /// Creates an empty slot.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            ptr: AtomicMaybeNull::null(),
+            _marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Installs `value` if the slot is still empty.
+    ///
+    /// Returns `None` on success. If the slot was already occupied, `value`
+    /// is handed back to the caller unchanged.
+    ///
+    /// Uses `Ordering::Release` on success and `Ordering::Acquire` on failure.
+    #[inline]
+    pub fn set_if_none(&self, value: alloc::boxed::Box<T>) -> Option<alloc::boxed::Box<T>> {
+        let new = alloc::boxed::Box::into_raw(value);
+        match self
+            .ptr
+            .compare_exchange(ptr::null_mut(), new, Ordering::Release, Ordering::Acquire)
+        {
+            Ok(_) => None,
+            Err(_) => Some(unsafe { alloc::boxed::Box::from_raw(new) }),
+        }
+    }
+
+    /// Returns a reference to the installed value, if any.
+    ///
+    /// Uses `Ordering::Acquire`.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        unsafe { self.ptr.get(Ordering::Acquire).map(|ptr| ptr.as_ref()) }
+    }
+
+    /// Removes and returns the installed value, if any.
+    ///
+    /// Takes `&mut self` rather than `&self`: reclaiming the allocation here
+    /// while a reference handed out by [`get`](Self::get) is still alive
+    /// would leave that reference dangling, so the borrow checker is used to
+    /// rule it out instead of requiring callers to reason about it.
+    #[inline]
+    pub fn take(&mut self) -> Option<alloc::boxed::Box<T>> {
+        self.ptr
+            .swap(ptr::null_mut(), Ordering::Acquire)
+            .get(Ordering::Relaxed)
+            .map(|ptr| unsafe { alloc::boxed::Box::from_raw(ptr.as_ptr()) })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Drop for AtomicSetOnce<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(ptr) = self.ptr.get(Ordering::Acquire) {
+            drop(unsafe { alloc::boxed::Box::from_raw(ptr.as_ptr()) });
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod atomic_set_once_tests {
+    use super::*;
+    use alloc::boxed::Box;
+    use core::sync::atomic::AtomicUsize;
+
+    struct DropCounter<'a>(&'a AtomicUsize);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn set_if_none_installs_the_value() {
+        let slot = AtomicSetOnce::new();
+        assert!(slot.set_if_none(Box::new(1)).is_none());
+        assert_eq!(slot.get(), Some(&1));
+    }
+
+    #[test]
+    fn set_if_none_returns_the_value_when_already_occupied() {
+        let slot = AtomicSetOnce::new();
+        assert!(slot.set_if_none(Box::new(1)).is_none());
+        let rejected = slot.set_if_none(Box::new(2));
+        assert_eq!(rejected.as_deref(), Some(&2));
+        assert_eq!(slot.get(), Some(&1));
+    }
+
+    #[test]
+    fn get_on_an_empty_slot_is_none() {
+        let slot = AtomicSetOnce::<i32>::new();
+        assert_eq!(slot.get(), None);
+    }
+
+    #[test]
+    fn take_removes_the_installed_value() {
+        let mut slot = AtomicSetOnce::new();
+        slot.set_if_none(Box::new(1));
+        assert_eq!(slot.take().as_deref(), Some(&1));
+        assert_eq!(slot.get(), None);
+        assert!(slot.take().is_none());
+    }
+
+    #[test]
+    fn drop_reclaims_an_installed_value_exactly_once() {
+        let drops = AtomicUsize::new(0);
+        {
+            let slot = AtomicSetOnce::new();
+            slot.set_if_none(Box::new(DropCounter(&drops)));
+        }
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn take_reclaims_exactly_once() {
+        let drops = AtomicUsize::new(0);
+        let mut slot = AtomicSetOnce::new();
+        slot.set_if_none(Box::new(DropCounter(&drops)));
+        drop(slot.take());
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+        drop(slot);
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+    }
 }